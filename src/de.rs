@@ -0,0 +1,582 @@
+use serde::de::{
+    self, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess,
+    Visitor,
+};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::io::Read;
+
+use crate::{
+    stream::{BinaryBorrowReader, BinaryReader, Event, XmlBorrowReader, XmlReader},
+    Error,
+};
+
+/// A structured plist deserializer, driven by a stream of [`Event`]s.
+///
+/// `'de` is the lifetime of data borrowed straight out of the input by `I`; deserializers built
+/// from a `Read` (see [`new_xml_deserializer`]/[`new_binary_deserializer`]) always produce owned
+/// events, so they instantiate this with `'de = 'static`.
+pub struct Deserializer<'de, I> {
+    events: I,
+    // An event that was pulled from `events` to decide how to dispatch but hasn't been
+    // consumed yet.
+    peeked: Option<Event<'de>>,
+    // Count of events pulled from `events` so far, used as a best-effort "position" for errors
+    // (such as a duplicate dictionary key) raised above the level of any particular `Reader`.
+    position: u64,
+    strict_duplicate_keys: bool,
+}
+
+impl<'de, I> Deserializer<'de, I>
+where
+    I: Iterator<Item = Result<Event<'de>, Error>>,
+{
+    pub fn new(events: I) -> Deserializer<'de, I> {
+        Deserializer {
+            events,
+            peeked: None,
+            position: 0,
+            strict_duplicate_keys: false,
+        }
+    }
+
+    /// When `strict` is `true`, a dictionary that repeats a key is rejected with
+    /// [`Error`] instead of silently letting the later value win. Defaults to `false`.
+    pub fn with_strict_duplicate_keys(mut self, strict: bool) -> Deserializer<'de, I> {
+        self.strict_duplicate_keys = strict;
+        self
+    }
+
+    fn next_event(&mut self) -> Result<Event<'de>, Error> {
+        match self.peeked.take() {
+            Some(event) => Ok(event),
+            None => {
+                let event = self.events.next().ok_or_else(Error::unexpected_eof)??;
+                self.position += 1;
+                Ok(event)
+            }
+        }
+    }
+
+    fn peek_event(&mut self) -> Result<&Event<'de>, Error> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.next_event()?);
+        }
+        Ok(self.peeked.as_ref().unwrap())
+    }
+
+    fn skip_value(&mut self) -> Result<(), Error> {
+        match self.next_event()? {
+            Event::StartArray(_) | Event::StartDictionary(_) => {
+                let mut depth = 1;
+                while depth > 0 {
+                    match self.next_event()? {
+                        Event::StartArray(_) | Event::StartDictionary(_) => depth += 1,
+                        Event::EndCollection => depth -= 1,
+                        _ => {}
+                    }
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+pub fn new_xml_deserializer<R: Read>(
+    reader: R,
+) -> Result<Deserializer<'static, XmlReader<R>>, Error> {
+    Ok(Deserializer::new(XmlReader::new(reader)?))
+}
+
+pub fn new_binary_deserializer<R: Read>(reader: R) -> Deserializer<'static, BinaryReader<R>> {
+    Deserializer::new(BinaryReader::new(reader))
+}
+
+/// Builds a deserializer that borrows string payloads directly out of `input` instead of
+/// allocating for each one, unless they need entity unescaping; `<data>` payloads are always
+/// owned. See [`XmlBorrowReader`].
+pub fn new_xml_borrow_deserializer(input: &str) -> Deserializer<'_, XmlBorrowReader<'_>> {
+    Deserializer::new(XmlBorrowReader::new(input))
+}
+
+/// Builds a deserializer that borrows string and data payloads directly out of `input` instead
+/// of allocating for each one. See [`BinaryBorrowReader`].
+pub fn new_binary_borrow_deserializer(input: &[u8]) -> Deserializer<'_, BinaryBorrowReader<'_>> {
+    Deserializer::new(BinaryBorrowReader::new(input))
+}
+
+macro_rules! forward_integer {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+            where V: Visitor<'de> {
+                match self.next_event()? {
+                    Event::Integer(i) => visitor.$visit(
+                        i.as_signed()
+                            .and_then(|v| v.try_into().ok())
+                            .or_else(|| i.as_unsigned().and_then(|v| v.try_into().ok()))
+                            .ok_or_else(|| Error::invalid_data("integer out of range", None))?,
+                    ),
+                    event => Err(unexpected_event(&event)),
+                }
+            }
+        )*
+    };
+}
+
+fn unexpected_event(event: &Event<'_>) -> Error {
+    Error::invalid_data(format!("unexpected event {:?}", event), None)
+}
+
+impl<'de, I> de::Deserializer<'de> for &mut Deserializer<'de, I>
+where
+    I: Iterator<Item = Result<Event<'de>, Error>>,
+{
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.peek_event()?.clone() {
+            Event::StartArray(_) => self.deserialize_seq(visitor),
+            Event::StartDictionary(_) => self.deserialize_map(visitor),
+            Event::Boolean(_) => self.deserialize_bool(visitor),
+            Event::Data(_) => self.deserialize_byte_buf(visitor),
+            Event::Integer(_) => self.deserialize_i64(visitor),
+            Event::Real(_) => self.deserialize_f64(visitor),
+            Event::String(_) => self.deserialize_string(visitor),
+            Event::Uid(_) | Event::EndCollection => Err(unexpected_event(self.peek_event()?)),
+        }
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.next_event()? {
+            Event::Boolean(v) => visitor.visit_bool(v),
+            event => Err(unexpected_event(&event)),
+        }
+    }
+
+    forward_integer! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.next_event()? {
+            Event::Real(v) => visitor.visit_f32(v as f32),
+            event => Err(unexpected_event(&event)),
+        }
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.next_event()? {
+            Event::Real(v) => visitor.visit_f64(v),
+            event => Err(unexpected_event(&event)),
+        }
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        // Prefer handing the visitor a reference straight into the input (`visit_borrowed_str`)
+        // over an owned `String`, but only when the event itself was borrowed to begin with.
+        match self.next_event()? {
+            Event::String(Cow::Borrowed(s)) => visitor.visit_borrowed_str(s),
+            Event::String(Cow::Owned(s)) => visitor.visit_string(s),
+            event => Err(unexpected_event(&event)),
+        }
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.next_event()? {
+            Event::Data(Cow::Borrowed(b)) => visitor.visit_borrowed_bytes(b),
+            Event::Data(Cow::Owned(b)) => visitor.visit_byte_buf(b),
+            event => Err(unexpected_event(&event)),
+        }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        // A `None` at the root serializes to no events at all.
+        if self.peeked.is_none() {
+            match self.events.next() {
+                None => return visitor.visit_none(),
+                Some(event) => {
+                    self.position += 1;
+                    self.peeked = Some(event?);
+                }
+            }
+        }
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.next_event()? {
+            Event::String(ref s) if s.is_empty() => visitor.visit_unit(),
+            event => Err(unexpected_event(&event)),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.next_event()? {
+            Event::StartArray(_) => {
+                let value = visitor.visit_seq(CollectionAccess::new(self))?;
+                self.finish_collection()?;
+                Ok(value)
+            }
+            event => Err(unexpected_event(&event)),
+        }
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.next_event()? {
+            Event::StartDictionary(_) => {
+                let value = visitor.visit_map(CollectionAccess::new(self))?;
+                self.finish_collection()?;
+                Ok(value)
+            }
+            event => Err(unexpected_event(&event)),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.peek_event()?.clone() {
+            Event::String(_) => visitor.visit_enum(UnitVariantAccess { de: self }),
+            Event::StartDictionary(_) => {
+                self.next_event()?;
+                let value = visitor.visit_enum(VariantAccessImpl { de: self })?;
+                self.finish_collection()?;
+                Ok(value)
+            }
+            event => Err(unexpected_event(&event)),
+        }
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.skip_value()?;
+        visitor.visit_unit()
+    }
+}
+
+impl<'de, I> Deserializer<'de, I>
+where
+    I: Iterator<Item = Result<Event<'de>, Error>>,
+{
+    fn finish_collection(&mut self) -> Result<(), Error> {
+        match self.next_event()? {
+            Event::EndCollection => Ok(()),
+            event => Err(unexpected_event(&event)),
+        }
+    }
+}
+
+struct CollectionAccess<'a, 'de, I> {
+    de: &'a mut Deserializer<'de, I>,
+    // Lazily populated the first time a key is seen, and only consulted when the deserializer is
+    // running in strict mode; see `Deserializer::with_strict_duplicate_keys`.
+    seen_keys: Option<HashSet<String>>,
+}
+
+impl<'a, 'de, I> CollectionAccess<'a, 'de, I> {
+    fn new(de: &'a mut Deserializer<'de, I>) -> CollectionAccess<'a, 'de, I> {
+        CollectionAccess {
+            de,
+            seen_keys: None,
+        }
+    }
+}
+
+impl<'a, 'de, I> SeqAccess<'de> for CollectionAccess<'a, 'de, I>
+where
+    I: Iterator<Item = Result<Event<'de>, Error>>,
+{
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if matches!(self.de.peek_event()?, Event::EndCollection) {
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+impl<'a, 'de, I> MapAccess<'de> for CollectionAccess<'a, 'de, I>
+where
+    I: Iterator<Item = Result<Event<'de>, Error>>,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if matches!(self.de.peek_event()?, Event::EndCollection) {
+            return Ok(None);
+        }
+        if self.de.strict_duplicate_keys {
+            if let Event::String(key) = self.de.peek_event()?.clone() {
+                let key = key.into_owned();
+                if !self.seen_keys.get_or_insert_with(HashSet::new).insert(key.clone()) {
+                    return Err(Error::duplicate_key(key, Some(self.de.position)));
+                }
+            }
+        }
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+struct UnitVariantAccess<'a, 'de, I> {
+    de: &'a mut Deserializer<'de, I>,
+}
+
+impl<'a, 'de, I> EnumAccess<'de> for UnitVariantAccess<'a, 'de, I>
+where
+    I: Iterator<Item = Result<Event<'de>, Error>>,
+{
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let name = match self.de.next_event()? {
+            Event::String(s) => s,
+            event => return Err(unexpected_event(&event)),
+        };
+        let value = seed.deserialize(name.into_deserializer())?;
+        Ok((value, self))
+    }
+}
+
+impl<'a, 'de, I> VariantAccess<'de> for UnitVariantAccess<'a, 'de, I>
+where
+    I: Iterator<Item = Result<Event<'de>, Error>>,
+{
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, _seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        Err(Error::invalid_data("expected unit variant", None))
+    }
+
+    fn tuple_variant<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::invalid_data("expected unit variant", None))
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::invalid_data("expected unit variant", None))
+    }
+}
+
+struct VariantAccessImpl<'a, 'de, I> {
+    de: &'a mut Deserializer<'de, I>,
+}
+
+impl<'a, 'de, I> EnumAccess<'de> for VariantAccessImpl<'a, 'de, I>
+where
+    I: Iterator<Item = Result<Event<'de>, Error>>,
+{
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let name = match self.de.next_event()? {
+            Event::String(s) => s,
+            event => return Err(unexpected_event(&event)),
+        };
+        let value = seed.deserialize(name.into_deserializer())?;
+        Ok((value, self))
+    }
+}
+
+impl<'a, 'de, I> VariantAccess<'de> for VariantAccessImpl<'a, 'de, I>
+where
+    I: Iterator<Item = Result<Event<'de>, Error>>,
+{
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        // Older encoders wrote a unit variant as `{variant: ""}`, like a newtype variant holding
+        // an empty string; accept that form too.
+        de::Deserializer::deserialize_unit(&mut *self.de, de::IgnoredAny).map(|_| ())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_tuple(&mut *self.de, len, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_struct(&mut *self.de, "", fields, visitor)
+    }
+}
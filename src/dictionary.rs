@@ -0,0 +1,115 @@
+use std::iter::FromIterator;
+
+use indexmap::map::{self, IndexMap};
+
+use crate::Value;
+
+/// A map of plist keys to values, as found inside a `<d>` (dictionary) element.
+///
+/// Backed by an [`IndexMap`], so iteration order follows insertion order rather than `String`'s
+/// `Ord` implementation: a plist read with [`from_reader`](crate::from_reader) and written back
+/// out with [`to_writer_xml`](crate::to_writer_xml) keeps its keys in their original order.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Dictionary(IndexMap<String, Value>);
+
+impl Dictionary {
+    /// Creates an empty `Dictionary`.
+    pub fn new() -> Self {
+        Dictionary(IndexMap::new())
+    }
+
+    /// Returns the number of entries in the dictionary.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the dictionary contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns a reference to the value corresponding to `key`, if present.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0.get(key)
+    }
+
+    /// Returns a mutable reference to the value corresponding to `key`, if present.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        self.0.get_mut(key)
+    }
+
+    /// Returns the `(key, value)` pair at `index`, in insertion order, if present.
+    pub fn get_index(&self, index: usize) -> Option<(&String, &Value)> {
+        self.0.get_index(index)
+    }
+
+    /// Returns `true` if the dictionary contains `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    /// Inserts `value` under `key`, returning the previous value if one was present.
+    ///
+    /// If `key` was already present, its position is left unchanged; otherwise the entry is
+    /// appended to the end of the dictionary.
+    pub fn insert(&mut self, key: String, value: Value) -> Option<Value> {
+        self.0.insert(key, value)
+    }
+
+    /// Removes `key` from the dictionary, returning its value if one was present.
+    ///
+    /// The remaining entries keep their relative order.
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        self.0.shift_remove(key)
+    }
+
+    /// Returns an iterator over the keys of the dictionary, in insertion order.
+    pub fn keys(&self) -> map::Keys<'_, String, Value> {
+        self.0.keys()
+    }
+
+    /// Returns an iterator over the values of the dictionary, in insertion order.
+    pub fn values(&self) -> map::Values<'_, String, Value> {
+        self.0.values()
+    }
+
+    /// Returns a mutable iterator over the values of the dictionary, in insertion order.
+    pub fn values_mut(&mut self) -> map::ValuesMut<'_, String, Value> {
+        self.0.values_mut()
+    }
+
+    /// Returns an iterator over the `(key, value)` pairs of the dictionary, in insertion order.
+    pub fn iter(&self) -> map::Iter<'_, String, Value> {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Dictionary {
+    type Item = (&'a String, &'a Value);
+    type IntoIter = map::Iter<'a, String, Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for Dictionary {
+    type Item = (String, Value);
+    type IntoIter = map::IntoIter<String, Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl FromIterator<(String, Value)> for Dictionary {
+    fn from_iter<T: IntoIterator<Item = (String, Value)>>(iter: T) -> Self {
+        Dictionary(IndexMap::from_iter(iter))
+    }
+}
+
+impl Extend<(String, Value)> for Dictionary {
+    fn extend<T: IntoIterator<Item = (String, Value)>>(&mut self, iter: T) {
+        self.0.extend(iter);
+    }
+}
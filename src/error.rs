@@ -0,0 +1,111 @@
+use std::{fmt, io};
+
+/// The error type for plist serialization and deserialization.
+#[derive(Debug)]
+pub struct Error(Box<ErrorImpl>);
+
+#[derive(Debug)]
+struct ErrorImpl {
+    kind: ErrorKind,
+    position: Option<u64>,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Io(io::Error),
+    UnexpectedEof,
+    InvalidData(String),
+    DuplicateKey(String),
+    #[cfg(feature = "serde")]
+    Serde(String),
+}
+
+impl Error {
+    pub(crate) fn invalid_data(msg: impl Into<String>, position: Option<u64>) -> Error {
+        Error(Box::new(ErrorImpl {
+            kind: ErrorKind::InvalidData(msg.into()),
+            position,
+        }))
+    }
+
+    pub(crate) fn unexpected_eof() -> Error {
+        Error(Box::new(ErrorImpl {
+            kind: ErrorKind::UnexpectedEof,
+            position: None,
+        }))
+    }
+
+    /// Used by [`Deserializer::with_strict_duplicate_keys`](crate::Deserializer) when a
+    /// dictionary repeats a key.
+    pub(crate) fn duplicate_key(key: impl Into<String>, position: Option<u64>) -> Error {
+        Error(Box::new(ErrorImpl {
+            kind: ErrorKind::DuplicateKey(key.into()),
+            position,
+        }))
+    }
+
+    /// The position in the input at which this error occurred, if it is known: a byte offset for
+    /// errors raised while reading, or, for a duplicate dictionary key, the number of events read
+    /// up to and including the repeated key.
+    pub fn position(&self) -> Option<u64> {
+        self.0.position
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0.kind {
+            ErrorKind::Io(err) => write!(f, "{}", err)?,
+            ErrorKind::UnexpectedEof => write!(f, "unexpected end of file")?,
+            ErrorKind::InvalidData(msg) => write!(f, "{}", msg)?,
+            ErrorKind::DuplicateKey(key) => write!(f, "duplicate dictionary key {:?}", key)?,
+            #[cfg(feature = "serde")]
+            ErrorKind::Serde(msg) => write!(f, "{}", msg)?,
+        }
+        if let Some(position) = self.0.position {
+            match &self.0.kind {
+                ErrorKind::DuplicateKey(_) => write!(f, " at event {}", position)?,
+                _ => write!(f, " at byte {}", position)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.0.kind {
+            ErrorKind::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error(Box::new(ErrorImpl {
+            kind: ErrorKind::Io(err),
+            position: None,
+        }))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Error {
+        Error(Box::new(ErrorImpl {
+            kind: ErrorKind::Serde(msg.to_string()),
+            position: None,
+        }))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Error {
+        Error(Box::new(ErrorImpl {
+            kind: ErrorKind::Serde(msg.to_string()),
+            position: None,
+        }))
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt;
+
+/// An integer that may be represented as either a signed or an unsigned 64-bit value.
+///
+/// Plists do not distinguish between signed and unsigned integers on the wire, but Rust's
+/// integer types do, so `Integer` remembers which representation was used to build it and
+/// lets callers query either one back out.
+#[derive(Copy, Clone)]
+pub struct Integer(IntegerRepr);
+
+// Two `Integer`s are equal when they hold the same numeric value, whichever representation
+// built them.
+impl PartialEq for Integer {
+    fn eq(&self, other: &Integer) -> bool {
+        self.as_signed() == other.as_signed() && self.as_unsigned() == other.as_unsigned()
+    }
+}
+
+impl Eq for Integer {}
+
+impl std::hash::Hash for Integer {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_signed().hash(state);
+        self.as_unsigned().hash(state);
+    }
+}
+
+#[derive(Copy, Clone, Eq, Hash, PartialEq)]
+enum IntegerRepr {
+    I64(i64),
+    U64(u64),
+}
+
+impl Integer {
+    /// Returns the value as an `i64` if it fits, regardless of which representation is stored.
+    pub fn as_signed(&self) -> Option<i64> {
+        match self.0 {
+            IntegerRepr::I64(v) => Some(v),
+            IntegerRepr::U64(v) => i64::try_from(v).ok(),
+        }
+    }
+
+    /// Returns the value as a `u64` if it fits, regardless of which representation is stored.
+    pub fn as_unsigned(&self) -> Option<u64> {
+        match self.0 {
+            IntegerRepr::I64(v) => u64::try_from(v).ok(),
+            IntegerRepr::U64(v) => Some(v),
+        }
+    }
+}
+
+impl fmt::Debug for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            IntegerRepr::I64(v) => fmt::Debug::fmt(&v, f),
+            IntegerRepr::U64(v) => fmt::Debug::fmt(&v, f),
+        }
+    }
+}
+
+macro_rules! impl_from_signed {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Integer {
+                fn from(value: $ty) -> Integer {
+                    Integer(IntegerRepr::I64(value as i64))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Integer {
+                fn from(value: $ty) -> Integer {
+                    Integer(IntegerRepr::U64(value as u64))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_signed!(i8, i16, i32, i64, isize);
+impl_from_unsigned!(u8, u16, u32, u64, usize);
@@ -0,0 +1,143 @@
+//! A `serde`-compatible reader and writer for the property list format used by Geometry Dash
+//! save data: plain old Apple plists, but with the abbreviated tag set (`<d>`, `<k>`, `<s>`, ...)
+//! GD itself writes.
+
+mod de;
+mod dictionary;
+mod error;
+mod integer;
+pub mod stream;
+mod ser;
+mod uid;
+mod value;
+
+#[cfg(test)]
+mod serde_tests;
+
+use std::io::{Read, Write};
+
+pub use crate::de::Deserializer;
+pub use crate::dictionary::Dictionary;
+pub use crate::error::Error;
+pub use crate::integer::Integer;
+pub use crate::ser::Serializer;
+pub use crate::stream::{Reader, Writer};
+pub use crate::uid::Uid;
+pub use crate::value::Value;
+
+use serde::{de::DeserializeOwned, ser::Serialize, Deserialize};
+
+/// Deserializes a value of type `T` from an XML or binary plist `reader`.
+pub fn from_reader<T, R>(mut reader: R) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+    R: Read,
+{
+    let mut magic = [0u8; 6];
+    let peeked = reader.read(&mut magic)?;
+    let reader = std::io::Cursor::new(&magic[..peeked]).chain(reader);
+
+    if &magic[..peeked] == b"gdbp00" {
+        let mut de = de::new_binary_deserializer(reader);
+        T::deserialize(&mut de)
+    } else {
+        let mut de = de::new_xml_deserializer(reader)?;
+        T::deserialize(&mut de)
+    }
+}
+
+/// Like [`from_reader`], but rejects a plist whose dictionaries repeat a key rather than letting
+/// the later value silently win.
+pub fn from_reader_strict<T, R>(mut reader: R) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+    R: Read,
+{
+    let mut magic = [0u8; 6];
+    let peeked = reader.read(&mut magic)?;
+    let reader = std::io::Cursor::new(&magic[..peeked]).chain(reader);
+
+    if &magic[..peeked] == b"gdbp00" {
+        let mut de = de::new_binary_deserializer(reader).with_strict_duplicate_keys(true);
+        T::deserialize(&mut de)
+    } else {
+        let mut de = de::new_xml_deserializer(reader)?.with_strict_duplicate_keys(true);
+        T::deserialize(&mut de)
+    }
+}
+
+/// Deserializes a value of type `T` from an in-memory XML or binary plist, borrowing string and
+/// data payloads directly out of `bytes` instead of allocating for each one wherever the
+/// encoding allows it (see [`stream::XmlBorrowReader`]/[`stream::BinaryBorrowReader`]).
+///
+/// Prefer [`from_reader`] when `T` doesn't need to borrow from the input, e.g. when streaming
+/// from a file.
+pub fn from_bytes<'de, T>(bytes: &'de [u8]) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    if bytes.starts_with(b"gdbp00") {
+        let mut de = de::new_binary_borrow_deserializer(bytes);
+        T::deserialize(&mut de)
+    } else {
+        let text = std::str::from_utf8(bytes)
+            .map_err(|_| Error::invalid_data("XML plist must be valid UTF-8", None))?;
+        let mut de = de::new_xml_borrow_deserializer(text);
+        T::deserialize(&mut de)
+    }
+}
+
+/// Like [`from_bytes`], but rejects a plist whose dictionaries repeat a key rather than letting
+/// the later value silently win.
+pub fn from_bytes_strict<'de, T>(bytes: &'de [u8]) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    if bytes.starts_with(b"gdbp00") {
+        let mut de = de::new_binary_borrow_deserializer(bytes).with_strict_duplicate_keys(true);
+        T::deserialize(&mut de)
+    } else {
+        let text = std::str::from_utf8(bytes)
+            .map_err(|_| Error::invalid_data("XML plist must be valid UTF-8", None))?;
+        let mut de = de::new_xml_borrow_deserializer(text).with_strict_duplicate_keys(true);
+        T::deserialize(&mut de)
+    }
+}
+
+/// Serializes `value` as an XML plist to `writer`.
+pub fn to_writer_xml<W, T>(writer: W, value: &T) -> Result<(), Error>
+where
+    W: Write,
+    T: ?Sized + Serialize,
+{
+    let mut ser = Serializer::new(stream::XmlWriter::new(writer));
+    value.serialize(&mut ser)
+}
+
+/// Serializes `value` as a binary plist to `writer`.
+pub fn to_writer_binary<W, T>(writer: W, value: &T) -> Result<(), Error>
+where
+    W: Write,
+    T: ?Sized + Serialize,
+{
+    let mut ser = Serializer::new(stream::BinaryWriter::new(writer));
+    value.serialize(&mut ser)
+}
+
+/// Copies every [`stream::Event`] from `reader` straight to `writer`, without ever materializing
+/// a [`Value`]/[`Dictionary`] tree in between.
+///
+/// This is the cheapest way to convert between encodings (e.g. XML to binary): `transcode`
+/// itself holds one event at a time. [`stream::BinaryReader`] streams its input, but
+/// [`stream::XmlReader`] reads the whole document into memory before yielding its first event,
+/// so transcoding from XML uses memory that grows with the size of the input.
+pub fn transcode<'a, R, W>(reader: R, mut writer: W) -> Result<(), Error>
+where
+    R: Reader<'a>,
+    W: Writer,
+{
+    for event in reader {
+        stream::forward_event(event?, &mut writer)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,576 @@
+use serde::ser::{
+    self, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant,
+};
+
+use std::borrow::Cow;
+
+use crate::stream::{forward_event, private::Sealed, Event};
+use crate::{Error, Integer, Writer};
+
+/// A structured plist serializer that emits [`Event`]s to a [`Writer`].
+pub struct Serializer<W> {
+    writer: W,
+}
+
+impl<W: Writer> Serializer<W> {
+    pub fn new(writer: W) -> Serializer<W> {
+        Serializer { writer }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// An in-memory [`Writer`] used to buffer a single value's events so [`StructSerializer`] can
+/// detect whether it serialized to nothing (a `None`) and, if so, omit the field entirely.
+#[derive(Default)]
+struct EventBuffer(Vec<Event<'static>>);
+
+impl Writer for EventBuffer {
+    fn write_start_array(&mut self, len: Option<u64>) -> Result<(), Error> {
+        self.0.push(Event::StartArray(len));
+        Ok(())
+    }
+
+    fn write_start_dictionary(&mut self, len: Option<u64>) -> Result<(), Error> {
+        self.0.push(Event::StartDictionary(len));
+        Ok(())
+    }
+
+    fn write_end_collection(&mut self) -> Result<(), Error> {
+        self.0.push(Event::EndCollection);
+        Ok(())
+    }
+
+    fn write_boolean(&mut self, value: bool) -> Result<(), Error> {
+        self.0.push(Event::Boolean(value));
+        Ok(())
+    }
+
+    fn write_data(&mut self, value: &[u8]) -> Result<(), Error> {
+        self.0.push(Event::Data(Cow::Owned(value.to_owned())));
+        Ok(())
+    }
+
+    fn write_integer(&mut self, value: Integer) -> Result<(), Error> {
+        self.0.push(Event::Integer(value));
+        Ok(())
+    }
+
+    fn write_real(&mut self, value: f64) -> Result<(), Error> {
+        self.0.push(Event::Real(value));
+        Ok(())
+    }
+
+    fn write_string(&mut self, value: &str) -> Result<(), Error> {
+        self.0.push(Event::String(Cow::Owned(value.to_owned())));
+        Ok(())
+    }
+
+    fn write_uid(&mut self, value: crate::Uid) -> Result<(), Error> {
+        self.0.push(Event::Uid(value));
+        Ok(())
+    }
+}
+
+impl Sealed for EventBuffer {}
+
+/// Wraps the main [`Serializer`] to disambiguate `Option<Option<T>>`.
+///
+/// Serde's blanket `impl Serialize for Option<T>` calls `serialize_some`/`serialize_none`
+/// uniformly regardless of what `T` is, so a plain `Some(x)` and a doubly-nested `Some(Some(x))`
+/// look identical from inside `serialize_some` unless the *contents* are serialized through a
+/// serializer that itself treats a further `Some`/`None` specially. This type is that inner
+/// serializer: used for the payload of a `Some` (including the payload of a `{"Some": ...}`
+/// marker) and for map keys and values, it wraps a `Some`/`None` in a `{"Some": ...}` /
+/// `{"None": ""}` marker dictionary, while every other value type passes through unchanged.
+struct NestedOptionSerializer<'a, W> {
+    ser: &'a mut Serializer<W>,
+}
+
+macro_rules! forward_to_ser {
+    ($($method:ident($($arg:ident: $ty:ty),*) -> $ret:ty),* $(,)?) => {
+        $(
+            fn $method(self, $($arg: $ty),*) -> Result<$ret, Error> {
+                ser::Serializer::$method(self.ser, $($arg),*)
+            }
+        )*
+    };
+}
+
+impl<'a, W: Writer> ser::Serializer for NestedOptionSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = <&'a mut Serializer<W> as ser::Serializer>::SerializeSeq;
+    type SerializeTuple = <&'a mut Serializer<W> as ser::Serializer>::SerializeTuple;
+    type SerializeTupleStruct = <&'a mut Serializer<W> as ser::Serializer>::SerializeTupleStruct;
+    type SerializeTupleVariant = <&'a mut Serializer<W> as ser::Serializer>::SerializeTupleVariant;
+    type SerializeMap = <&'a mut Serializer<W> as ser::Serializer>::SerializeMap;
+    type SerializeStruct = <&'a mut Serializer<W> as ser::Serializer>::SerializeStruct;
+    type SerializeStructVariant =
+        <&'a mut Serializer<W> as ser::Serializer>::SerializeStructVariant;
+
+    forward_to_ser! {
+        serialize_bool(v: bool) -> (),
+        serialize_i8(v: i8) -> (),
+        serialize_i16(v: i16) -> (),
+        serialize_i32(v: i32) -> (),
+        serialize_i64(v: i64) -> (),
+        serialize_u8(v: u8) -> (),
+        serialize_u16(v: u16) -> (),
+        serialize_u32(v: u32) -> (),
+        serialize_u64(v: u64) -> (),
+        serialize_f32(v: f32) -> (),
+        serialize_f64(v: f64) -> (),
+        serialize_char(v: char) -> (),
+        serialize_str(v: &str) -> (),
+        serialize_bytes(v: &[u8]) -> (),
+        serialize_unit() -> (),
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.ser.writer.write_start_dictionary(Some(1))?;
+        self.ser.writer.write_string("None")?;
+        self.ser.writer.write_string("")?;
+        self.ser.writer.write_end_collection()
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.ser.writer.write_start_dictionary(Some(1))?;
+        self.ser.writer.write_string("Some")?;
+        value.serialize(NestedOptionSerializer { ser: &mut *self.ser })?;
+        self.ser.writer.write_end_collection()
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<(), Error> {
+        ser::Serializer::serialize_unit_struct(self.ser, name)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        ser::Serializer::serialize_unit_variant(self.ser, name, variant_index, variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::Serializer::serialize_newtype_struct(self.ser, name, value)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::Serializer::serialize_newtype_variant(self.ser, name, variant_index, variant, value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        ser::Serializer::serialize_seq(self.ser, len)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Error> {
+        ser::Serializer::serialize_tuple(self.ser, len)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        ser::Serializer::serialize_tuple_struct(self.ser, name, len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        ser::Serializer::serialize_tuple_variant(self.ser, name, variant_index, variant, len)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        ser::Serializer::serialize_map(self.ser, len)
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        ser::Serializer::serialize_struct(self.ser, name, len)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        ser::Serializer::serialize_struct_variant(self.ser, name, variant_index, variant, len)
+    }
+}
+
+impl<'a, W: Writer> ser::Serializer for &'a mut Serializer<W> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = CollectionSerializer<'a, W>;
+    type SerializeTuple = CollectionSerializer<'a, W>;
+    type SerializeTupleStruct = CollectionSerializer<'a, W>;
+    type SerializeTupleVariant = CollectionSerializer<'a, W>;
+    type SerializeMap = CollectionSerializer<'a, W>;
+    type SerializeStruct = StructSerializer<'a, W>;
+    type SerializeStructVariant = StructSerializer<'a, W>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.writer.write_boolean(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.writer.write_integer(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.writer.write_integer(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.writer.write_integer(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.writer.write_integer(v.into())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.writer.write_integer(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.writer.write_integer(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.writer.write_integer(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.writer.write_integer(v.into())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        self.writer.write_real(v as f64)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        self.writer.write_real(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        let mut buf = [0u8; 4];
+        self.writer.write_string(v.encode_utf8(&mut buf))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        self.writer.write_string(v)
+    }
+
+    /// Emits a plist `<data>` element instead of going through the generic sequence path, so
+    /// that `Vec<u8>` and `#[serde(with = "serde_bytes")]` fields round-trip as compact binary
+    /// data rather than as an array of per-byte integers.
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        self.writer.write_data(v)
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(NestedOptionSerializer { ser: self })
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.writer.write_string("")
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.writer.write_string(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.writer.write_start_dictionary(Some(1))?;
+        self.writer.write_string(variant)?;
+        value.serialize(&mut *self)?;
+        self.writer.write_end_collection()
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        self.writer.write_start_array(len.map(|len| len as u64))?;
+        Ok(CollectionSerializer { ser: self })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        self.writer.write_start_dictionary(Some(1))?;
+        self.writer.write_string(variant)?;
+        self.writer.write_start_array(Some(len as u64))?;
+        Ok(CollectionSerializer { ser: self })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        self.writer.write_start_dictionary(len.map(|len| len as u64))?;
+        Ok(CollectionSerializer { ser: self })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        self.writer.write_start_dictionary(None)?;
+        Ok(StructSerializer { ser: self, wrap: false })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        self.writer.write_start_dictionary(Some(1))?;
+        self.writer.write_string(variant)?;
+        self.writer.write_start_dictionary(None)?;
+        Ok(StructSerializer { ser: self, wrap: true })
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Shared [`SerializeSeq`]/[`SerializeTuple`]/[`SerializeMap`] implementation: arrays, tuples
+/// and maps all just forward each element straight to the writer.
+pub struct CollectionSerializer<'a, W> {
+    ser: &'a mut Serializer<W>,
+}
+
+impl<'a, W: Writer> SerializeSeq for CollectionSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.ser.writer.write_end_collection()
+    }
+}
+
+impl<'a, W: Writer> SerializeTuple for CollectionSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl<'a, W: Writer> SerializeTupleStruct for CollectionSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl<'a, W: Writer> SerializeTupleVariant for CollectionSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        // Closes the array, then the single-entry `{variant: [...]}` wrapper dictionary.
+        self.ser.writer.write_end_collection()?;
+        self.ser.writer.write_end_collection()
+    }
+}
+
+impl<'a, W: Writer> SerializeMap for CollectionSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        key.serialize(NestedOptionSerializer { ser: &mut *self.ser })
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(NestedOptionSerializer { ser: &mut *self.ser })
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.ser.writer.write_end_collection()
+    }
+}
+
+/// [`SerializeStruct`]/[`SerializeStructVariant`] implementation.
+///
+/// Each field is first serialized into a scratch [`EventBuffer`]; a field whose value produces
+/// no events (i.e. a `None`) is omitted from the dictionary entirely instead of writing a key
+/// with a missing value.
+pub struct StructSerializer<'a, W> {
+    ser: &'a mut Serializer<W>,
+    // Struct variants open two dictionaries (the `{variant: {...}}` wrapper and the fields
+    // themselves) and so must close both on `end`.
+    wrap: bool,
+}
+
+impl<'a, W: Writer> StructSerializer<'a, W> {
+    fn serialize_entry<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut buffer = Serializer::new(EventBuffer::default());
+        value.serialize(&mut buffer)?;
+        let events = buffer.into_inner().0;
+        if events.is_empty() {
+            return Ok(());
+        }
+        self.ser.writer.write_string(key)?;
+        for event in events {
+            forward_event(event, &mut self.ser.writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, W: Writer> SerializeStruct for StructSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_entry(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.ser.writer.write_end_collection()?;
+        if self.wrap {
+            self.ser.writer.write_end_collection()?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, W: Writer> SerializeStructVariant for StructSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_entry(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        SerializeStruct::end(self)
+    }
+}
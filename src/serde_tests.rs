@@ -24,6 +24,11 @@ impl VecWriter {
 }
 
 impl Writer for VecWriter {
+    fn write_start_array(&mut self, len: Option<u64>) -> Result<(), Error> {
+        self.events.push(Event::StartArray(len));
+        Ok(())
+    }
+
     fn write_start_dictionary(&mut self, len: Option<u64>) -> Result<(), Error> {
         self.events.push(Event::StartDictionary(len));
         Ok(())
@@ -39,6 +44,11 @@ impl Writer for VecWriter {
         Ok(())
     }
 
+    fn write_data(&mut self, value: &[u8]) -> Result<(), Error> {
+        self.events.push(Event::Data(value.to_owned().into()));
+        Ok(())
+    }
+
     fn write_integer(&mut self, value: Integer) -> Result<(), Error> {
         self.events.push(Event::Integer(value));
         Ok(())
@@ -66,12 +76,15 @@ fn new_serializer() -> Serializer<VecWriter> {
     Serializer::new(VecWriter::new())
 }
 
-fn new_deserializer(events: Vec<OwnedEvent>) -> Deserializer<Vec<Result<OwnedEvent, Error>>> {
+fn new_deserializer(
+    events: Vec<OwnedEvent>,
+) -> Deserializer<'static, std::vec::IntoIter<Result<OwnedEvent, Error>>> {
     let result_events = events.into_iter().map(Ok).collect();
+    let result_events = Vec::into_iter(result_events);
     Deserializer::new(result_events)
 }
 
-fn assert_roundtrip<T>(obj: T, comparison: Option<&[Event]>)
+fn assert_roundtrip<T>(obj: T, comparison: Option<&[Event<'_>]>)
 where
     T: Debug + DeserializeOwned + PartialEq + Serialize,
 {
@@ -573,3 +586,137 @@ fn serde_yaml_to_value() {
     let value: Value = serde_yaml::from_str("true").unwrap();
     assert_eq!(value, Value::Boolean(true));
 }
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct WithBytes {
+    #[serde(with = "serde_bytes")]
+    thumbnail: Vec<u8>,
+}
+
+#[test]
+fn bytes_serialize_as_data_event() {
+    // Byte sequences should go through `serialize_bytes`/`Event::Data`, not the generic seq
+    // path, so they round-trip as a single `<data>` element rather than an array of integers.
+    let obj = WithBytes {
+        thumbnail: vec![0, 1, 2, 0xFF],
+    };
+
+    let comparison = &[
+        Event::StartDictionary(None),
+        Event::String("thumbnail".into()),
+        Event::Data(vec![0, 1, 2, 0xFF].into()),
+        Event::EndCollection,
+    ];
+
+    assert_roundtrip(obj, Some(comparison));
+}
+
+#[test]
+fn from_bytes_borrows_strings_without_entities() {
+    #[derive(Deserialize)]
+    struct Item<'a> {
+        name: &'a str,
+    }
+
+    let xml = b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<plist version=\"1.0\">\n\
+        <d>\n<k>name</k>\n<s>Spider</s>\n</d>\n</plist>";
+    let item: Item = crate::from_bytes(xml).unwrap();
+
+    assert_eq!(item.name, "Spider");
+    // No entity-unescaping was needed, so the deserializer should have borrowed straight out of
+    // `xml` instead of allocating a new `String`.
+    let xml_range = xml.as_ptr() as usize..xml.as_ptr() as usize + xml.len();
+    assert!(xml_range.contains(&(item.name.as_ptr() as usize)));
+}
+
+#[test]
+fn from_bytes_borrows_binary_data() {
+    #[derive(Deserialize)]
+    struct Item<'a> {
+        #[serde(with = "serde_bytes")]
+        thumbnail: std::borrow::Cow<'a, [u8]>,
+    }
+
+    let mut buf = Vec::new();
+    crate::to_writer_binary(
+        &mut buf,
+        &WithBytes {
+            thumbnail: vec![1, 2, 3],
+        },
+    )
+    .unwrap();
+
+    let item: Item = crate::from_bytes(&buf).unwrap();
+    assert_eq!(&*item.thumbnail, &[1, 2, 3]);
+
+    // The binary encoding stores `<data>` payloads as a raw length-prefixed byte span with no
+    // decoding step, so the deserializer should hand back a slice straight into `buf`.
+    let buf_range = buf.as_ptr() as usize..buf.as_ptr() as usize + buf.len();
+    assert!(buf_range.contains(&(item.thumbnail.as_ptr() as usize)));
+}
+
+#[test]
+fn transcode_xml_to_binary() {
+    let dog = Animal::Dog(Dog {
+        a: (),
+        b: 12,
+        c: Some(Uid::new(42)),
+    });
+
+    let mut xml = Cursor::new(Vec::new());
+    crate::to_writer_xml(&mut xml, &dog).unwrap();
+    let xml = xml.into_inner();
+
+    // Convert XML straight to binary, event by event, without building a `Value` in between.
+    let reader = crate::stream::XmlReader::new(Cursor::new(xml)).unwrap();
+    let mut binary = Cursor::new(Vec::new());
+    crate::transcode(reader, crate::stream::BinaryWriter::new(&mut binary)).unwrap();
+
+    let roundtripped: Animal = crate::from_reader(Cursor::new(binary.into_inner())).unwrap();
+    assert_eq!(roundtripped, dog);
+}
+
+#[test]
+fn strict_duplicate_keys_are_rejected() {
+    let xml = br#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<d>
+<k>name</k>
+<s>first</s>
+<k>name</k>
+<s>second</s>
+</d>
+</plist>"#;
+
+    let lax: Dictionary = crate::from_bytes(xml).unwrap();
+    assert_eq!(lax.get("name").unwrap().as_string().unwrap(), "second");
+
+    let err = crate::from_bytes_strict::<Dictionary>(xml).unwrap_err();
+    assert!(err.to_string().contains("name"));
+}
+
+#[test]
+fn dictionary_preserves_key_order() {
+    let xml = br#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<d>
+<k>zebra</k>
+<s>z</s>
+<k>apple</k>
+<s>a</s>
+<k>mango</k>
+<s>m</s>
+</d>
+</plist>"#;
+
+    let dict: Dictionary = crate::from_bytes(xml).unwrap();
+    let keys: Vec<&str> = dict.keys().map(String::as_str).collect();
+    assert_eq!(keys, ["zebra", "apple", "mango"]);
+    assert_eq!(dict.get_index(1).unwrap().0, "apple");
+
+    let mut roundtripped = Cursor::new(Vec::new());
+    crate::to_writer_xml(&mut roundtripped, &dict).unwrap();
+    let roundtripped: Dictionary = crate::from_bytes(&roundtripped.into_inner()).unwrap();
+    let roundtripped_keys: Vec<&str> = roundtripped.keys().map(String::as_str).collect();
+    assert_eq!(roundtripped_keys, ["zebra", "apple", "mango"]);
+}
@@ -0,0 +1,150 @@
+use std::io::Read;
+
+use std::borrow::Cow;
+
+use super::private::Sealed;
+use super::{Event, OwnedEvent};
+use crate::{Error, Integer, Uid};
+
+const MAGIC: &[u8] = b"gdbp00";
+
+const TAG_START_ARRAY: u8 = 0xA0;
+const TAG_START_DICT: u8 = 0xD0;
+const TAG_END_COLLECTION: u8 = 0xFF;
+const TAG_FALSE: u8 = 0x00;
+const TAG_TRUE: u8 = 0x01;
+const TAG_DATA: u8 = 0x40;
+const TAG_INT_SIGNED: u8 = 0x10;
+const TAG_INT_UNSIGNED: u8 = 0x11;
+const TAG_REAL: u8 = 0x20;
+const TAG_STRING: u8 = 0x50;
+// Emitted only by foreign encoders; this crate's own `BinaryWriter` always writes `TAG_STRING`
+// (UTF-8), but readers still need to accept UTF-16BE strings produced elsewhere.
+const TAG_STRING_UTF16: u8 = 0x51;
+const TAG_UID: u8 = 0x80;
+
+/// Reads the binary encoding produced by [`BinaryWriter`](super::BinaryWriter).
+pub struct BinaryReader<R: Read> {
+    reader: R,
+    pos: u64,
+    checked_magic: bool,
+}
+
+impl<R: Read> BinaryReader<R> {
+    pub fn new(reader: R) -> BinaryReader<R> {
+        BinaryReader {
+            reader,
+            pos: 0,
+            checked_magic: false,
+        }
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        self.reader.read_exact(buf).map_err(|_| Error::unexpected_eof())?;
+        self.pos += buf.len() as u64;
+        Ok(())
+    }
+
+    fn read_u64(&mut self) -> Result<u64, Error> {
+        let mut buf = [0u8; 8];
+        self.read_exact(&mut buf)?;
+        Ok(u64::from_be_bytes(buf))
+    }
+
+    fn read_i64(&mut self) -> Result<i64, Error> {
+        let mut buf = [0u8; 8];
+        self.read_exact(&mut buf)?;
+        Ok(i64::from_be_bytes(buf))
+    }
+
+    fn read_f64(&mut self) -> Result<f64, Error> {
+        let mut buf = [0u8; 8];
+        self.read_exact(&mut buf)?;
+        Ok(f64::from_be_bytes(buf))
+    }
+
+    fn read_len_prefixed(&mut self) -> Result<Vec<u8>, Error> {
+        let len = self.read_u64()? as usize;
+        let mut buf = vec![0u8; len];
+        self.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn read_tag(&mut self) -> Result<Option<u8>, Error> {
+        if !self.checked_magic {
+            let mut magic = [0u8; 6];
+            if self.reader.read_exact(&mut magic).is_err() {
+                return Ok(None);
+            }
+            self.pos += 6;
+            if magic != MAGIC {
+                return Err(Error::invalid_data("bad binary plist magic", Some(self.pos)));
+            }
+            self.checked_magic = true;
+        }
+        let mut tag = [0u8; 1];
+        match self.reader.read(&mut tag) {
+            Ok(0) => Ok(None),
+            Ok(_) => {
+                self.pos += 1;
+                Ok(Some(tag[0]))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn read_event(&mut self, tag: u8) -> Result<OwnedEvent, Error> {
+        match tag {
+            TAG_START_ARRAY => {
+                let len = self.read_u64()?;
+                Ok(Event::StartArray((len != u64::MAX).then_some(len)))
+            }
+            TAG_START_DICT => {
+                let len = self.read_u64()?;
+                Ok(Event::StartDictionary((len != u64::MAX).then_some(len)))
+            }
+            TAG_END_COLLECTION => Ok(Event::EndCollection),
+            TAG_FALSE => Ok(Event::Boolean(false)),
+            TAG_TRUE => Ok(Event::Boolean(true)),
+            TAG_DATA => Ok(Event::Data(Cow::Owned(self.read_len_prefixed()?))),
+            TAG_INT_SIGNED => Ok(Event::Integer(Integer::from(self.read_i64()?))),
+            TAG_INT_UNSIGNED => Ok(Event::Integer(Integer::from(self.read_u64()?))),
+            TAG_REAL => Ok(Event::Real(self.read_f64()?)),
+            TAG_STRING => {
+                let bytes = self.read_len_prefixed()?;
+                String::from_utf8(bytes)
+                    .map(|s| Event::String(Cow::Owned(s)))
+                    .map_err(|_| Error::invalid_data("invalid UTF-8 string", Some(self.pos)))
+            }
+            TAG_STRING_UTF16 => {
+                let bytes = self.read_len_prefixed()?;
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect();
+                String::from_utf16(&units)
+                    .map(|s| Event::String(Cow::Owned(s)))
+                    .map_err(|_| Error::invalid_data("invalid UTF-16BE string", Some(self.pos)))
+            }
+            TAG_UID => Ok(Event::Uid(Uid::new(self.read_u64()?))),
+            _ => Err(Error::invalid_data(
+                format!("unknown binary plist tag 0x{:02x}", tag),
+                Some(self.pos),
+            )),
+        }
+    }
+}
+
+impl<R: Read> Iterator for BinaryReader<R> {
+    type Item = Result<OwnedEvent, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_tag() {
+            Ok(Some(tag)) => Some(self.read_event(tag)),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+impl<R: Read> Sealed for BinaryReader<R> {}
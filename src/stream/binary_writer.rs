@@ -0,0 +1,133 @@
+use std::io::Write;
+
+use super::private::Sealed;
+use super::Writer;
+use crate::{Error, Integer, Uid};
+
+const MAGIC: &[u8] = b"gdbp00";
+
+// Tags for the simplified binary encoding below. Unlike Apple's `bplist00` format this does not
+// build an object table with back-references; every value is written inline, which is cheaper to
+// implement and is good enough for the save files this crate targets (Geometry Dash plists are
+// small and mostly flat).
+const TAG_START_ARRAY: u8 = 0xA0;
+const TAG_START_DICT: u8 = 0xD0;
+const TAG_END_COLLECTION: u8 = 0xFF;
+const TAG_FALSE: u8 = 0x00;
+const TAG_TRUE: u8 = 0x01;
+const TAG_DATA: u8 = 0x40;
+const TAG_INT_SIGNED: u8 = 0x10;
+const TAG_INT_UNSIGNED: u8 = 0x11;
+const TAG_REAL: u8 = 0x20;
+const TAG_STRING: u8 = 0x50;
+const TAG_UID: u8 = 0x80;
+
+/// Writes [`Event`](super::Event)s as a compact binary encoding.
+pub struct BinaryWriter<W: Write> {
+    writer: W,
+    started: bool,
+}
+
+impl<W: Write> BinaryWriter<W> {
+    pub fn new(writer: W) -> BinaryWriter<W> {
+        BinaryWriter {
+            writer,
+            started: false,
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn ensure_header(&mut self) -> Result<(), Error> {
+        if !self.started {
+            self.writer.write_all(MAGIC)?;
+            self.started = true;
+        }
+        Ok(())
+    }
+
+    fn write_len(&mut self, len: Option<u64>) -> Result<(), Error> {
+        // `u64::MAX` is reserved to mean "unknown length" for collections written from a
+        // streaming source that can't look ahead.
+        self.writer.write_all(&len.unwrap_or(u64::MAX).to_be_bytes())?;
+        Ok(())
+    }
+
+    fn write_bytes_with_len(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        self.writer.write_all(&(bytes.len() as u64).to_be_bytes())?;
+        self.writer.write_all(bytes)?;
+        Ok(())
+    }
+}
+
+impl<W: Write> Writer for BinaryWriter<W> {
+    fn write_start_array(&mut self, len: Option<u64>) -> Result<(), Error> {
+        self.ensure_header()?;
+        self.writer.write_all(&[TAG_START_ARRAY])?;
+        self.write_len(len)
+    }
+
+    fn write_start_dictionary(&mut self, len: Option<u64>) -> Result<(), Error> {
+        self.ensure_header()?;
+        self.writer.write_all(&[TAG_START_DICT])?;
+        self.write_len(len)
+    }
+
+    fn write_end_collection(&mut self) -> Result<(), Error> {
+        self.ensure_header()?;
+        self.writer.write_all(&[TAG_END_COLLECTION])?;
+        Ok(())
+    }
+
+    fn write_boolean(&mut self, value: bool) -> Result<(), Error> {
+        self.ensure_header()?;
+        self.writer.write_all(&[if value { TAG_TRUE } else { TAG_FALSE }])?;
+        Ok(())
+    }
+
+    fn write_data(&mut self, value: &[u8]) -> Result<(), Error> {
+        self.ensure_header()?;
+        self.writer.write_all(&[TAG_DATA])?;
+        self.write_bytes_with_len(value)
+    }
+
+    fn write_integer(&mut self, value: Integer) -> Result<(), Error> {
+        self.ensure_header()?;
+        match value.as_signed() {
+            Some(v) => {
+                self.writer.write_all(&[TAG_INT_SIGNED])?;
+                self.writer.write_all(&v.to_be_bytes())?;
+            }
+            None => {
+                self.writer.write_all(&[TAG_INT_UNSIGNED])?;
+                self.writer
+                    .write_all(&value.as_unsigned().unwrap_or_default().to_be_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_real(&mut self, value: f64) -> Result<(), Error> {
+        self.ensure_header()?;
+        self.writer.write_all(&[TAG_REAL])?;
+        self.writer.write_all(&value.to_be_bytes())?;
+        Ok(())
+    }
+
+    fn write_string(&mut self, value: &str) -> Result<(), Error> {
+        self.ensure_header()?;
+        self.writer.write_all(&[TAG_STRING])?;
+        self.write_bytes_with_len(value.as_bytes())
+    }
+
+    fn write_uid(&mut self, value: Uid) -> Result<(), Error> {
+        self.ensure_header()?;
+        self.writer.write_all(&[TAG_UID])?;
+        self.writer.write_all(&value.get().to_be_bytes())?;
+        Ok(())
+    }
+}
+
+impl<W: Write> Sealed for BinaryWriter<W> {}
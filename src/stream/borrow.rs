@@ -0,0 +1,290 @@
+//! Readers that borrow directly from an in-memory buffer instead of allocating a fresh `String`
+//! or `Vec<u8>` for every string/data payload they produce.
+
+use std::borrow::Cow;
+
+use super::private::Sealed;
+use super::Event;
+use crate::{Error, Integer, Uid};
+
+enum Tag<'de> {
+    Open(&'de str),
+    Close(&'de str),
+    SelfClosing(&'de str),
+}
+
+/// Reads the abbreviated XML plist dialect used by Geometry Dash save data directly from a
+/// `&'de str`, the borrowing counterpart to [`XmlReader`](super::XmlReader).
+///
+/// String payloads are handed out as `Cow::Borrowed` slices of the input whenever they don't
+/// contain an XML entity; a string with an entity (`&amp;`, `&lt;`, ...) still needs unescaping
+/// into a fresh `String`, so it falls back to `Cow::Owned`. `<data>` elements are always owned,
+/// since base64-decoding inherently produces new bytes.
+pub struct XmlBorrowReader<'de> {
+    input: &'de str,
+    pos: usize,
+}
+
+impl<'de> XmlBorrowReader<'de> {
+    pub fn new(input: &'de str) -> XmlBorrowReader<'de> {
+        XmlBorrowReader { input, pos: 0 }
+    }
+
+    fn position(&self) -> u64 {
+        self.pos as u64
+    }
+
+    fn skip_until_tag(&mut self) {
+        while self.pos < self.input.len() && !self.input[self.pos..].starts_with('<') {
+            self.pos += 1;
+        }
+    }
+
+    fn next_tag(&mut self) -> Result<Option<Tag<'de>>, Error> {
+        self.skip_until_tag();
+        if self.pos >= self.input.len() {
+            return Ok(None);
+        }
+        let rest = &self.input[self.pos..];
+        let end = rest
+            .find('>')
+            .ok_or_else(|| Error::invalid_data("unterminated tag", Some(self.position())))?;
+        let raw = &rest[1..end];
+        self.pos += end + 1;
+
+        if let Some(name) = raw.strip_prefix('/') {
+            Ok(Some(Tag::Close(name)))
+        } else if let Some(name) = raw.strip_suffix('/') {
+            Ok(Some(Tag::SelfClosing(name)))
+        } else if raw.starts_with('?') || raw.starts_with('!') {
+            self.next_tag()
+        } else {
+            Ok(Some(Tag::Open(raw.split_whitespace().next().unwrap_or(raw))))
+        }
+    }
+
+    fn read_text_until_close(&mut self, tag: &str) -> Result<Cow<'de, str>, Error> {
+        let rest = &self.input[self.pos..];
+        let closer = format!("</{}>", tag);
+        let end = rest.find(&closer).ok_or_else(|| {
+            Error::invalid_data(format!("missing closing tag for <{}>", tag), Some(self.position()))
+        })?;
+        let text = &rest[..end];
+        self.pos += end + closer.len();
+        Ok(if text.contains('&') {
+            Cow::Owned(unescape(text))
+        } else {
+            Cow::Borrowed(text)
+        })
+    }
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+impl<'de> Iterator for XmlBorrowReader<'de> {
+    type Item = Result<Event<'de>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let tag = match self.next_tag() {
+                Ok(Some(tag)) => tag,
+                Ok(None) => return None,
+                Err(err) => return Some(Err(err)),
+            };
+
+            return Some(match tag {
+                Tag::Open("plist") | Tag::Close("plist") => continue,
+                Tag::Open("d") => Ok(Event::StartDictionary(None)),
+                Tag::Open("a") => Ok(Event::StartArray(None)),
+                Tag::Close("d") | Tag::Close("a") => Ok(Event::EndCollection),
+                Tag::SelfClosing("t") => Ok(Event::Boolean(true)),
+                Tag::SelfClosing("f") => Ok(Event::Boolean(false)),
+                Tag::Open(name @ ("k" | "s")) => self.read_text_until_close(name).map(Event::String),
+                Tag::Open("i") => self.read_text_until_close("i").and_then(|s| {
+                    s.parse::<i64>()
+                        .map(Integer::from)
+                        .or_else(|_| s.parse::<u64>().map(Integer::from))
+                        .map(Event::Integer)
+                        .map_err(|_| Error::invalid_data("invalid <i> body", Some(self.position())))
+                }),
+                Tag::Open("r") => self
+                    .read_text_until_close("r")
+                    .and_then(|s| {
+                        s.parse::<f64>()
+                            .map_err(|_| Error::invalid_data("invalid <r> body", Some(self.position())))
+                    })
+                    .map(Event::Real),
+                Tag::Open("data") => self
+                    .read_text_until_close("data")
+                    .and_then(|s| {
+                        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+                        BASE64
+                            .decode(s.trim())
+                            .map_err(|_| Error::invalid_data("invalid <data> body", Some(self.position())))
+                    })
+                    .map(|data| Event::Data(Cow::Owned(data))),
+                Tag::Open("u") => self
+                    .read_text_until_close("u")
+                    .and_then(|s| {
+                        s.parse::<u64>()
+                            .map_err(|_| Error::invalid_data("invalid <u> body", Some(self.position())))
+                    })
+                    .map(Uid::new)
+                    .map(Event::Uid),
+                Tag::Open(name) => Err(Error::invalid_data(
+                    format!("unknown tag <{}>", name),
+                    Some(self.position()),
+                )),
+                Tag::Close(_) | Tag::SelfClosing(_) => continue,
+            });
+        }
+    }
+}
+
+impl<'de> Sealed for XmlBorrowReader<'de> {}
+
+const MAGIC: &[u8] = b"gdbp00";
+
+const TAG_START_ARRAY: u8 = 0xA0;
+const TAG_START_DICT: u8 = 0xD0;
+const TAG_END_COLLECTION: u8 = 0xFF;
+const TAG_FALSE: u8 = 0x00;
+const TAG_TRUE: u8 = 0x01;
+const TAG_DATA: u8 = 0x40;
+const TAG_INT_SIGNED: u8 = 0x10;
+const TAG_INT_UNSIGNED: u8 = 0x11;
+const TAG_REAL: u8 = 0x20;
+const TAG_STRING: u8 = 0x50;
+const TAG_STRING_UTF16: u8 = 0x51;
+const TAG_UID: u8 = 0x80;
+
+/// Reads the binary encoding produced by [`BinaryWriter`](super::BinaryWriter) directly from a
+/// `&'de [u8]`, the borrowing counterpart to [`BinaryReader`](super::BinaryReader).
+///
+/// Unlike the XML dialect, binary strings and data are stored length-prefixed with no escaping,
+/// so ASCII/UTF-8 strings and all `<data>`-equivalent blobs borrow straight from the input.
+/// UTF-16BE strings (written only by other encoders; this crate's own
+/// [`BinaryWriter`](super::BinaryWriter) never
+/// emits them) must still be converted to UTF-8 and so fall back to `Cow::Owned`.
+pub struct BinaryBorrowReader<'de> {
+    input: &'de [u8],
+    pos: usize,
+    checked_magic: bool,
+}
+
+impl<'de> BinaryBorrowReader<'de> {
+    pub fn new(input: &'de [u8]) -> BinaryBorrowReader<'de> {
+        BinaryBorrowReader {
+            input,
+            pos: 0,
+            checked_magic: false,
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'de [u8], Error> {
+        let bytes = self
+            .input
+            .get(self.pos..self.pos + len)
+            .ok_or_else(Error::unexpected_eof)?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn read_u64(&mut self) -> Result<u64, Error> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn read_i64(&mut self) -> Result<i64, Error> {
+        Ok(i64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn read_f64(&mut self) -> Result<f64, Error> {
+        Ok(f64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn read_len_prefixed(&mut self) -> Result<&'de [u8], Error> {
+        let len = self.read_u64()? as usize;
+        self.take(len)
+    }
+
+    fn read_tag(&mut self) -> Result<Option<u8>, Error> {
+        if !self.checked_magic {
+            if self.input.len() < MAGIC.len() {
+                return Ok(None);
+            }
+            let magic = self.take(MAGIC.len())?;
+            if magic != MAGIC {
+                return Err(Error::invalid_data("bad binary plist magic", Some(self.pos as u64)));
+            }
+            self.checked_magic = true;
+        }
+        match self.input.get(self.pos) {
+            Some(&tag) => {
+                self.pos += 1;
+                Ok(Some(tag))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn read_event(&mut self, tag: u8) -> Result<Event<'de>, Error> {
+        match tag {
+            TAG_START_ARRAY => {
+                let len = self.read_u64()?;
+                Ok(Event::StartArray((len != u64::MAX).then_some(len)))
+            }
+            TAG_START_DICT => {
+                let len = self.read_u64()?;
+                Ok(Event::StartDictionary((len != u64::MAX).then_some(len)))
+            }
+            TAG_END_COLLECTION => Ok(Event::EndCollection),
+            TAG_FALSE => Ok(Event::Boolean(false)),
+            TAG_TRUE => Ok(Event::Boolean(true)),
+            TAG_DATA => Ok(Event::Data(Cow::Borrowed(self.read_len_prefixed()?))),
+            TAG_INT_SIGNED => Ok(Event::Integer(Integer::from(self.read_i64()?))),
+            TAG_INT_UNSIGNED => Ok(Event::Integer(Integer::from(self.read_u64()?))),
+            TAG_REAL => Ok(Event::Real(self.read_f64()?)),
+            TAG_STRING => {
+                let bytes = self.read_len_prefixed()?;
+                std::str::from_utf8(bytes)
+                    .map(|s| Event::String(Cow::Borrowed(s)))
+                    .map_err(|_| Error::invalid_data("invalid UTF-8 string", Some(self.pos as u64)))
+            }
+            TAG_STRING_UTF16 => {
+                let bytes = self.read_len_prefixed()?;
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect();
+                String::from_utf16(&units)
+                    .map(|s| Event::String(Cow::Owned(s)))
+                    .map_err(|_| Error::invalid_data("invalid UTF-16BE string", Some(self.pos as u64)))
+            }
+            TAG_UID => Ok(Event::Uid(Uid::new(self.read_u64()?))),
+            _ => Err(Error::invalid_data(
+                format!("unknown binary plist tag 0x{:02x}", tag),
+                Some(self.pos as u64),
+            )),
+        }
+    }
+}
+
+impl<'de> Iterator for BinaryBorrowReader<'de> {
+    type Item = Result<Event<'de>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_tag() {
+            Ok(Some(tag)) => Some(self.read_event(tag)),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+impl<'de> Sealed for BinaryBorrowReader<'de> {}
@@ -0,0 +1,83 @@
+//! Low level, event-based plist (de)serialization primitives shared by the XML and binary
+//! codecs.
+
+use std::borrow::Cow;
+
+use crate::{Error, Integer, Uid};
+
+pub(crate) mod private {
+    /// Prevents downstream crates from implementing [`Writer`](super::Writer) for their own
+    /// types.
+    pub trait Sealed {}
+}
+
+mod binary_reader;
+mod binary_writer;
+mod borrow;
+mod xml_reader;
+mod xml_writer;
+
+pub use self::binary_reader::BinaryReader;
+pub use self::binary_writer::BinaryWriter;
+pub use self::borrow::{BinaryBorrowReader, XmlBorrowReader};
+pub use self::xml_reader::XmlReader;
+pub use self::xml_writer::XmlWriter;
+
+/// A single token in the stream representation of a plist.
+///
+/// String and data payloads borrow from the input (`Cow::Borrowed`) whenever the underlying
+/// reader can hand them out without a copy; otherwise they own their data (`Cow::Owned`). See
+/// [`XmlBorrowReader`] and [`BinaryBorrowReader`] for the readers that take advantage of this.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event<'a> {
+    StartArray(Option<u64>),
+    StartDictionary(Option<u64>),
+    EndCollection,
+    Boolean(bool),
+    Data(Cow<'a, [u8]>),
+    Integer(Integer),
+    Real(f64),
+    String(Cow<'a, str>),
+    Uid(Uid),
+}
+
+/// An [`Event`] that owns all of its data.
+pub type OwnedEvent = Event<'static>;
+
+/// A consumer of plist [`Event`]s, implemented once per on-disk encoding (XML, binary, ...).
+///
+/// This trait is sealed: it can only be implemented by writers defined in this crate.
+pub trait Writer: private::Sealed {
+    fn write_start_array(&mut self, len: Option<u64>) -> Result<(), Error>;
+    fn write_start_dictionary(&mut self, len: Option<u64>) -> Result<(), Error>;
+    fn write_end_collection(&mut self) -> Result<(), Error>;
+    fn write_boolean(&mut self, value: bool) -> Result<(), Error>;
+    fn write_data(&mut self, value: &[u8]) -> Result<(), Error>;
+    fn write_integer(&mut self, value: Integer) -> Result<(), Error>;
+    fn write_real(&mut self, value: f64) -> Result<(), Error>;
+    fn write_string(&mut self, value: &str) -> Result<(), Error>;
+    fn write_uid(&mut self, value: Uid) -> Result<(), Error>;
+}
+
+/// A producer of plist [`Event`]s, implemented once per on-disk encoding (XML, binary, ...).
+pub trait Reader<'a>: Iterator<Item = Result<Event<'a>, Error>> + private::Sealed {}
+
+impl<'a, T> Reader<'a> for T where T: Iterator<Item = Result<Event<'a>, Error>> + private::Sealed {}
+
+/// Forwards a single event to the matching `write_*` method on `writer`.
+///
+/// Shared by the serializer's field-buffering and by [`crate::transcode`] so the two don't
+/// drift out of sync whenever a new `Event` variant is added.
+pub(crate) fn forward_event(event: Event<'_>, writer: &mut impl Writer) -> Result<(), Error> {
+    match event {
+        Event::StartArray(len) => writer.write_start_array(len),
+        Event::StartDictionary(len) => writer.write_start_dictionary(len),
+        Event::EndCollection => writer.write_end_collection(),
+        Event::Boolean(v) => writer.write_boolean(v),
+        Event::Data(v) => writer.write_data(&v),
+        Event::Integer(v) => writer.write_integer(v),
+        Event::Real(v) => writer.write_real(v),
+        Event::String(v) => writer.write_string(&v),
+        Event::Uid(v) => writer.write_uid(v),
+    }
+}
@@ -0,0 +1,154 @@
+use std::borrow::Cow;
+use std::io::Read;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+use super::private::Sealed;
+use super::{Event, OwnedEvent};
+use crate::{Error, Integer, Uid};
+
+enum Tag {
+    Open(String),
+    Close(String),
+    SelfClosing(String),
+}
+
+/// Reads the abbreviated XML plist dialect used by Geometry Dash save data, producing a
+/// stream of [`Event`]s.
+///
+/// This is a minimal, non-validating tokenizer: it understands exactly the tag set this crate
+/// writes (`<d>`, `<a>`, `<k>`, `<s>`, `<i>`, `<r>`, `<t/>`, `<f/>`, `<data>`, `<u>`, plus the `<plist>`
+/// wrapper) and does not attempt to handle arbitrary XML such as comments or namespaces.
+pub struct XmlReader<R: Read> {
+    // The whole document, decoded up front; plists are small enough in practice that streaming
+    // the raw bytes buys little and makes entity/tag splitting much simpler to get right.
+    buf: String,
+    pos: usize,
+}
+
+impl<R: Read> XmlReader<R> {
+    pub fn new(mut reader: R) -> Result<XmlReader<R>, Error> {
+        let mut buf = String::new();
+        reader.read_to_string(&mut buf)?;
+        Ok(XmlReader { buf, pos: 0 })
+    }
+
+    fn position(&self) -> u64 {
+        self.pos as u64
+    }
+
+    fn skip_until_tag(&mut self) {
+        while self.pos < self.buf.len() && !self.buf[self.pos..].starts_with('<') {
+            self.pos += 1;
+        }
+    }
+
+    fn next_tag(&mut self) -> Result<Option<Tag>, Error> {
+        self.skip_until_tag();
+        if self.pos >= self.buf.len() {
+            return Ok(None);
+        }
+        let rest = &self.buf[self.pos..];
+        let end = rest
+            .find('>')
+            .ok_or_else(|| Error::invalid_data("unterminated tag", Some(self.position())))?;
+        let raw = &rest[1..end];
+        self.pos += end + 1;
+
+        if let Some(name) = raw.strip_prefix('/') {
+            Ok(Some(Tag::Close(name.to_owned())))
+        } else if let Some(name) = raw.strip_suffix('/') {
+            Ok(Some(Tag::SelfClosing(name.to_owned())))
+        } else if raw.starts_with('?') || raw.starts_with('!') {
+            // `<?xml ... ?>` and `<!DOCTYPE ...>`: skip and move on to the next real tag.
+            self.next_tag()
+        } else {
+            Ok(Some(Tag::Open(raw.split_whitespace().next().unwrap_or(raw).to_owned())))
+        }
+    }
+
+    fn read_text_until_close(&mut self, tag: &str) -> Result<String, Error> {
+        let rest = &self.buf[self.pos..];
+        let closer = format!("</{}>", tag);
+        let end = rest.find(&closer).ok_or_else(|| {
+            Error::invalid_data(format!("missing closing tag for <{}>", tag), Some(self.position()))
+        })?;
+        let text = unescape(&rest[..end]);
+        self.pos += end + closer.len();
+        Ok(text)
+    }
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+impl<R: Read> Iterator for XmlReader<R> {
+    type Item = Result<OwnedEvent, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let tag = match self.next_tag() {
+                Ok(Some(tag)) => tag,
+                Ok(None) => return None,
+                Err(err) => return Some(Err(err)),
+            };
+
+            return Some(match tag {
+                Tag::Open(name) if name == "plist" => continue,
+                Tag::Close(name) if name == "plist" => continue,
+                Tag::Open(name) if name == "d" => Ok(Event::StartDictionary(None)),
+                Tag::Open(name) if name == "a" => Ok(Event::StartArray(None)),
+                Tag::Close(name) if name == "d" || name == "a" => Ok(Event::EndCollection),
+                Tag::SelfClosing(name) if name == "t" => Ok(Event::Boolean(true)),
+                Tag::SelfClosing(name) if name == "f" => Ok(Event::Boolean(false)),
+                Tag::Open(name) if name == "k" || name == "s" => self
+                    .read_text_until_close(&name)
+                    .map(|s| Event::String(Cow::Owned(s))),
+                Tag::Open(name) if name == "i" => self.read_text_until_close(&name).and_then(|s| {
+                    s.parse::<i64>()
+                        .map(Integer::from)
+                        .or_else(|_| s.parse::<u64>().map(Integer::from))
+                        .map(Event::Integer)
+                        .map_err(|_| Error::invalid_data("invalid <i> body", Some(self.position())))
+                }),
+                Tag::Open(name) if name == "r" => self
+                    .read_text_until_close(&name)
+                    .and_then(|s| {
+                        s.parse::<f64>()
+                            .map_err(|_| Error::invalid_data("invalid <r> body", Some(self.position())))
+                    })
+                    .map(Event::Real),
+                Tag::Open(name) if name == "data" => self
+                    .read_text_until_close(&name)
+                    .and_then(|s| {
+                        BASE64
+                            .decode(s.trim())
+                            .map_err(|_| Error::invalid_data("invalid <data> body", Some(self.position())))
+                    })
+                    .map(|data| Event::Data(Cow::Owned(data))),
+                Tag::Open(name) if name == "u" => self
+                    .read_text_until_close(&name)
+                    .and_then(|s| {
+                        s.parse::<u64>()
+                            .map_err(|_| Error::invalid_data("invalid <u> body", Some(self.position())))
+                    })
+                    .map(Uid::new)
+                    .map(Event::Uid),
+                Tag::Open(name) => Err(Error::invalid_data(
+                    format!("unknown tag <{}>", name),
+                    Some(self.position()),
+                )),
+                Tag::Close(_) | Tag::SelfClosing(_) => {
+                    continue;
+                }
+            });
+        }
+    }
+}
+
+impl<R: Read> Sealed for XmlReader<R> {}
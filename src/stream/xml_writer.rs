@@ -0,0 +1,155 @@
+use std::io::Write;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+use super::private::Sealed;
+use super::Writer;
+use crate::{Error, Integer, Uid};
+
+const HEADER: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+<plist version=\"1.0\">\n";
+
+enum Frame {
+    Array,
+    // Whether the *next* token written directly inside this dictionary is a key.
+    Dict(bool),
+}
+
+/// Writes [`Event`](super::Event)s as the abbreviated XML plist dialect used by Geometry Dash
+/// save data (`<d>`/`<k>`/`<s>`/`<i>`/`<r>`/`<t/>`/`<f/>` instead of the verbose Apple tag
+/// names).
+pub struct XmlWriter<W: Write> {
+    writer: W,
+    started: bool,
+    stack: Vec<Frame>,
+}
+
+impl<W: Write> XmlWriter<W> {
+    pub fn new(writer: W) -> XmlWriter<W> {
+        XmlWriter {
+            writer,
+            started: false,
+            stack: Vec::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn ensure_header(&mut self) -> Result<(), Error> {
+        if !self.started {
+            self.writer.write_all(HEADER.as_bytes())?;
+            self.started = true;
+        }
+        Ok(())
+    }
+
+    fn indent(&mut self) -> Result<(), Error> {
+        for _ in 0..self.stack.len() {
+            self.writer.write_all(b"\t")?;
+        }
+        Ok(())
+    }
+
+    // Called before writing any token directly inside the current collection. Returns whether
+    // this token is a dictionary key (only meaningful for `write_string`).
+    fn next_is_key(&mut self) -> bool {
+        match self.stack.last_mut() {
+            Some(Frame::Dict(expect_key)) => {
+                let is_key = *expect_key;
+                *expect_key = !*expect_key;
+                is_key
+            }
+            Some(Frame::Array) | None => false,
+        }
+    }
+
+    fn write_tag_line(&mut self, tag: &str, body: &str) -> Result<(), Error> {
+        self.next_is_key();
+        self.ensure_header()?;
+        self.indent()?;
+        writeln!(self.writer, "<{tag}>{body}</{tag}>", tag = tag, body = body)?;
+        Ok(())
+    }
+
+    fn write_empty_tag_line(&mut self, tag: &str) -> Result<(), Error> {
+        self.next_is_key();
+        self.ensure_header()?;
+        self.indent()?;
+        writeln!(self.writer, "<{tag}/>", tag = tag)?;
+        Ok(())
+    }
+}
+
+impl<W: Write> Writer for XmlWriter<W> {
+    fn write_start_array(&mut self, _len: Option<u64>) -> Result<(), Error> {
+        self.next_is_key();
+        self.ensure_header()?;
+        self.indent()?;
+        self.writer.write_all(b"<a>\n")?;
+        self.stack.push(Frame::Array);
+        Ok(())
+    }
+
+    fn write_start_dictionary(&mut self, _len: Option<u64>) -> Result<(), Error> {
+        self.next_is_key();
+        self.ensure_header()?;
+        self.indent()?;
+        self.writer.write_all(b"<d>\n")?;
+        self.stack.push(Frame::Dict(true));
+        Ok(())
+    }
+
+    fn write_end_collection(&mut self) -> Result<(), Error> {
+        let frame = self
+            .stack
+            .pop()
+            .expect("write_end_collection called without a matching start");
+        self.indent()?;
+        match frame {
+            Frame::Array => self.writer.write_all(b"</a>\n")?,
+            Frame::Dict(_) => self.writer.write_all(b"</d>\n")?,
+        }
+        if self.stack.is_empty() {
+            self.writer.write_all(b"</plist>")?;
+        }
+        Ok(())
+    }
+
+    fn write_boolean(&mut self, value: bool) -> Result<(), Error> {
+        self.write_empty_tag_line(if value { "t" } else { "f" })
+    }
+
+    fn write_data(&mut self, value: &[u8]) -> Result<(), Error> {
+        self.write_tag_line("data", &BASE64.encode(value))
+    }
+
+    fn write_integer(&mut self, value: Integer) -> Result<(), Error> {
+        let body = match value.as_signed() {
+            Some(v) => v.to_string(),
+            None => value.as_unsigned().unwrap_or_default().to_string(),
+        };
+        self.write_tag_line("i", &body)
+    }
+
+    fn write_real(&mut self, value: f64) -> Result<(), Error> {
+        self.write_tag_line("r", &value.to_string())
+    }
+
+    fn write_string(&mut self, value: &str) -> Result<(), Error> {
+        let is_key = self.next_is_key();
+        self.ensure_header()?;
+        self.indent()?;
+        let tag = if is_key { "k" } else { "s" };
+        writeln!(self.writer, "<{tag}>{value}</{tag}>", tag = tag, value = value)?;
+        Ok(())
+    }
+
+    fn write_uid(&mut self, value: Uid) -> Result<(), Error> {
+        self.write_tag_line("u", &value.get().to_string())
+    }
+}
+
+impl<W: Write> Sealed for XmlWriter<W> {}
@@ -0,0 +1,16 @@
+/// A unique identifier, as used by `NSKeyedArchiver`-style plists to reference other objects
+/// in the same archive by index.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Uid(u64);
+
+impl Uid {
+    /// Creates a new `Uid` from its raw integer value.
+    pub fn new(id: u64) -> Uid {
+        Uid(id)
+    }
+
+    /// Returns the raw integer value of this `Uid`.
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+}
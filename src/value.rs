@@ -0,0 +1,127 @@
+use crate::{Dictionary, Integer, Uid};
+
+/// Any plist value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Array(Vec<Value>),
+    Dictionary(Dictionary),
+    Boolean(bool),
+    Data(Vec<u8>),
+    Integer(Integer),
+    Real(f64),
+    String(String),
+    Uid(Uid),
+}
+
+impl Value {
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Value::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+
+    pub fn as_dictionary(&self) -> Option<&Dictionary> {
+        match self {
+            Value::Dictionary(dict) => Some(dict),
+            _ => None,
+        }
+    }
+
+    pub fn as_boolean(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_data(&self) -> Option<&[u8]> {
+        match self {
+            Value::Data(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    pub fn as_real(&self) -> Option<f64> {
+        match self {
+            Value::Real(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_signed_integer(&self) -> Option<i64> {
+        match self {
+            Value::Integer(i) => i.as_signed(),
+            _ => None,
+        }
+    }
+
+    pub fn as_unsigned_integer(&self) -> Option<u64> {
+        match self {
+            Value::Integer(i) => i.as_unsigned(),
+            _ => None,
+        }
+    }
+
+    pub fn as_string(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_uid(&self) -> Option<&Uid> {
+        match self {
+            Value::Uid(uid) => Some(uid),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(v: bool) -> Value {
+        Value::Boolean(v)
+    }
+}
+
+impl From<Integer> for Value {
+    fn from(v: Integer) -> Value {
+        Value::Integer(v)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(v: f64) -> Value {
+        Value::Real(v)
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Value {
+        Value::String(v)
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(v: Vec<u8>) -> Value {
+        Value::Data(v)
+    }
+}
+
+impl From<Dictionary> for Value {
+    fn from(v: Dictionary) -> Value {
+        Value::Dictionary(v)
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(v: Vec<Value>) -> Value {
+        Value::Array(v)
+    }
+}
+
+impl From<Uid> for Value {
+    fn from(v: Uid) -> Value {
+        Value::Uid(v)
+    }
+}